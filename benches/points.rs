@@ -0,0 +1,60 @@
+//! Projects a million points through a perspective transform with the scalar batch method and
+//! the `simd` one. Without the `simd` feature both take the scalar path, so run with
+//! `cargo bench --features simd --bench points` to see the difference.
+//!
+//! Uses only std, so the numbers are rough.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use transformator::Transform;
+
+const POINTS: usize = 1_000_000;
+const FRAMES: u32 = 20;
+
+type BatchFn = fn(&Transform, &[f32], &[f32], &mut [f32], &mut [f32]);
+
+fn run(name: &str, transform: &Transform, batch: BatchFn) -> Duration {
+    let xs: Vec<f32> = (0..POINTS).map(|i| (i % 1000) as f32 * 0.1).collect();
+    let ys: Vec<f32> = (0..POINTS).map(|i| (i / 1000) as f32 * 0.1).collect();
+    let (mut out_x, mut out_y) = (vec![0.0; POINTS], vec![0.0; POINTS]);
+
+    // The fastest frame is the least disturbed by the rest of the system
+    let mut elapsed = Duration::MAX;
+    for _ in 0..FRAMES {
+        let start = Instant::now();
+        batch(transform, &xs, &ys, &mut out_x, &mut out_y);
+        black_box((&out_x, &out_y));
+        elapsed = elapsed.min(start.elapsed());
+    }
+
+    let per_point = elapsed.as_nanos() as f64 / POINTS as f64;
+    println!("{:<10} {:>8.2} ns per point", name, per_point);
+    elapsed
+}
+
+fn main() {
+    let transform = Transform::new()
+        .with_parent_container_perspective(500.0, 400.0, 300.0)
+        .with_position_relative_to_parent(350.0, 250.0)
+        .with_origin(50.0, 50.0)
+        .then_rotate_y_deg(30.0)
+        .then_rotate_x_deg(45.0)
+        .compose_2(&Transform::new());
+
+    let scalar = run(
+        "scalar",
+        &transform,
+        Transform::transform_local_points2d_to_world,
+    );
+    let simd = run(
+        "simd",
+        &transform,
+        Transform::transform_local_points2d_to_world_simd,
+    );
+    println!(
+        "{:<10} {:>8.2}x (simd feature {})",
+        "speedup",
+        scalar.as_secs_f64() / simd.as_secs_f64(),
+        if cfg!(feature = "simd") { "on" } else { "off" }
+    );
+}
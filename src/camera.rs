@@ -0,0 +1,100 @@
+use crate::Transform;
+#[cfg(feature = "serialization")]
+use serde::{Deserialize, Serialize};
+
+/// A scene-wide camera that owns the perspective parameters shared by all top-level elements.
+///
+/// Instead of calling [`Transform::with_parent_container_perspective`] on every top-level element,
+/// compose them against [`Camera::root_transform`]. Changing the camera's distance or origin then
+/// updates the whole scene from one place.
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Camera {
+    /// Perspective distance, same as the CSS `perspective` property
+    pub distance: f32,
+    /// Perspective origin (vanishing point), same as the CSS `perspective-origin` property
+    pub origin: (f32, f32),
+    /// Transform of the scene root the perspective is applied on top of
+    pub root: Transform,
+}
+
+impl Camera {
+    pub fn new(distance: f32, origin_x: f32, origin_y: f32) -> Self {
+        Self {
+            distance,
+            origin: (origin_x, origin_y),
+            root: Transform::new(),
+        }
+    }
+
+    pub fn set_distance(&mut self, distance: f32) {
+        self.distance = distance;
+    }
+
+    pub fn with_distance(mut self, distance: f32) -> Self {
+        self.set_distance(distance);
+        self
+    }
+
+    pub fn set_origin(&mut self, origin_x: f32, origin_y: f32) {
+        self.origin = (origin_x, origin_y);
+    }
+
+    pub fn with_origin(mut self, origin_x: f32, origin_y: f32) -> Self {
+        self.set_origin(origin_x, origin_y);
+        self
+    }
+
+    /// Sets the scene root. The root should be composed before calling [`Camera::root_transform`].
+    pub fn set_root(&mut self, root: Transform) {
+        self.root = root;
+    }
+
+    pub fn with_root(mut self, root: Transform) -> Self {
+        self.set_root(root);
+        self
+    }
+
+    /// Returns a composed transform that top-level elements should compose against. Its world
+    /// transform contains the camera perspective followed by the root's world transform.
+    pub fn root_transform(&self) -> Transform {
+        Transform::new()
+            .with_parent_container_perspective(self.distance, self.origin.0, self.origin.1)
+            .compose_2(&self.root)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::Camera;
+    use crate::Transform;
+
+    #[test]
+    pub fn test_root_transform_matches_per_element_perspective() {
+        let camera = Camera::new(500.0, 400.0, 300.0);
+
+        let with_camera = Transform::new()
+            .with_position_relative_to_parent(350.0, 250.0)
+            .with_origin(50.0, 50.0)
+            .then_rotate_x_deg(45.0)
+            .compose_2(&camera.root_transform());
+
+        let per_element = Transform::new()
+            .with_position_relative_to_parent(350.0, 250.0)
+            .with_parent_container_perspective(500.0, 400.0, 300.0)
+            .with_origin(50.0, 50.0)
+            .then_rotate_x_deg(45.0)
+            .compose_2(&Transform::new());
+
+        for (x, y) in [(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)] {
+            let a = with_camera.transform_local_point2d_to_world(x, y);
+            let b = per_element.transform_local_point2d_to_world(x, y);
+            assert!(
+                (a.0 - b.0).abs() < 0.01 && (a.1 - b.1).abs() < 0.01,
+                "Camera point deviated: got {:?}, expected {:?}",
+                a,
+                b
+            );
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Conversions between [`Transform`] and the flat matrix layouts expected by graphics APIs.
+//!
+//! euclid (and therefore this crate) uses row vectors: a point is transformed as `p * M`, and the
+//! translation lives in the last row (`m41`, `m42`, `m43`). OpenGL uses column vectors
+//! (`M * p`) with column-major storage, DirectX uses row vectors with row-major storage. Both end
+//! up with the exact same 16 floats in memory, with the translation at indices 12, 13 and 14.
+//! The separate functions exist so call sites document which API they are feeding.
+//!
+//! The `to_*` functions export the composed world transform. The `from_*` functions put the
+//! matrix into the local transform of a new element, compose it against an identity root to get
+//! the same world transform back.
+
+use crate::Transform;
+use euclid::Transform3D;
+
+/// Returns the world transform in the layout expected by OpenGL's `glUniformMatrix4fv` with
+/// `transpose` set to `GL_FALSE` (column-major, column vectors).
+///
+/// ```rust
+/// use transformator::{convert, Transform};
+///
+/// let transform = Transform::new()
+///     .then_translate_3d(10.0, 20.0, 30.0)
+///     .compose_2(&Transform::new());
+///
+/// let m = convert::to_opengl(&transform);
+/// // The translation is stored in the fourth column, which is the last 4 floats in memory
+/// assert_eq!(&m[12..16], &[10.0, 20.0, 30.0, 1.0]);
+/// ```
+pub fn to_opengl(m: &Transform) -> [f32; 16] {
+    m.world_transform.to_array()
+}
+
+/// Creates a transform from a column-major OpenGL matrix. The inverse of [`to_opengl`].
+///
+/// ```rust
+/// use transformator::{convert, Transform};
+///
+/// let m = [
+///     1.0, 0.0, 0.0, 0.0, //
+///     0.0, 1.0, 0.0, 0.0, //
+///     0.0, 0.0, 1.0, 0.0, //
+///     10.0, 20.0, 30.0, 1.0,
+/// ];
+/// let transform = convert::from_opengl(m).compose_2(&Transform::new());
+/// assert_eq!(transform.transform_local_point2d_to_world(0.0, 0.0), (10.0, 20.0));
+/// ```
+pub fn from_opengl(m: [f32; 16]) -> Transform {
+    from_array(m)
+}
+
+/// Returns the world transform in the layout expected by DirectX (`XMFLOAT4X4` / D3DX matrices,
+/// row-major, row vectors).
+///
+/// ```rust
+/// use transformator::{convert, Transform};
+///
+/// let transform = Transform::new()
+///     .then_translate_3d(10.0, 20.0, 30.0)
+///     .compose_2(&Transform::new());
+///
+/// let m = convert::to_directx(&transform);
+/// // The translation is stored in the fourth row (`_41`, `_42`, `_43`)
+/// assert_eq!(&m[12..16], &[10.0, 20.0, 30.0, 1.0]);
+/// ```
+pub fn to_directx(m: &Transform) -> [f32; 16] {
+    m.world_transform.to_array()
+}
+
+/// Creates a transform from a row-major DirectX matrix. The inverse of [`to_directx`].
+///
+/// ```rust
+/// use transformator::{convert, Transform};
+///
+/// let m = [
+///     1.0, 0.0, 0.0, 0.0, //
+///     0.0, 1.0, 0.0, 0.0, //
+///     0.0, 0.0, 1.0, 0.0, //
+///     10.0, 20.0, 30.0, 1.0,
+/// ];
+/// let transform = convert::from_directx(m).compose_2(&Transform::new());
+/// assert_eq!(transform.transform_local_point2d_to_world(0.0, 0.0), (10.0, 20.0));
+/// ```
+pub fn from_directx(m: [f32; 16]) -> Transform {
+    from_array(m)
+}
+
+fn from_array(m: [f32; 16]) -> Transform {
+    let mut transform = Transform::new();
+    transform.local_transform = Transform3D::from_array(m);
+    transform
+}
@@ -0,0 +1,691 @@
+//! Parsing and printing of CSS `transform` function lists, e.g.
+//! `"translate(10px, 20px) rotateX(45deg) scale(2)"`.
+//!
+//! CSS applies the functions of a list right to left, so the last function is applied to the
+//! element first. `Transform::new().then_rotate_x_deg(45.0).then_translate(10.0, 0.0)` is
+//! therefore written as `"translate(10px, 0px) rotateX(45deg)"` in CSS.
+
+use crate::Transform;
+use euclid::{Angle, Transform3D, UnknownUnit};
+use std::fmt;
+
+/// A single CSS transform function.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransformOp {
+    Translate(f32, f32),
+    TranslateX(f32),
+    TranslateY(f32),
+    TranslateZ(f32),
+    Translate3d(f32, f32, f32),
+    Scale(f32, f32),
+    ScaleX(f32),
+    ScaleY(f32),
+    ScaleZ(f32),
+    Scale3d(f32, f32, f32),
+    Rotate(Angle<f32>),
+    RotateX(Angle<f32>),
+    RotateY(Angle<f32>),
+    RotateZ(Angle<f32>),
+    Rotate3d(f32, f32, f32, Angle<f32>),
+    Skew(Angle<f32>, Angle<f32>),
+    SkewX(Angle<f32>),
+    SkewY(Angle<f32>),
+    Perspective(f32),
+    /// `matrix(a, b, c, d, e, f)`
+    Matrix([f32; 6]),
+    /// `matrix3d(...)`, in CSS (column-major) argument order
+    Matrix3d([f32; 16]),
+}
+
+impl TransformOp {
+    /// Returns the matrix of this function in euclid's row vector convention.
+    pub fn to_matrix(&self) -> Transform3D<f32, UnknownUnit, UnknownUnit> {
+        match *self {
+            TransformOp::Translate(x, y) => Transform3D::translation(x, y, 0.0),
+            TransformOp::TranslateX(x) => Transform3D::translation(x, 0.0, 0.0),
+            TransformOp::TranslateY(y) => Transform3D::translation(0.0, y, 0.0),
+            TransformOp::TranslateZ(z) => Transform3D::translation(0.0, 0.0, z),
+            TransformOp::Translate3d(x, y, z) => Transform3D::translation(x, y, z),
+            TransformOp::Scale(x, y) => Transform3D::scale(x, y, 1.0),
+            TransformOp::ScaleX(x) => Transform3D::scale(x, 1.0, 1.0),
+            TransformOp::ScaleY(y) => Transform3D::scale(1.0, y, 1.0),
+            TransformOp::ScaleZ(z) => Transform3D::scale(1.0, 1.0, z),
+            TransformOp::Scale3d(x, y, z) => Transform3D::scale(x, y, z),
+            TransformOp::Rotate(angle) | TransformOp::RotateZ(angle) => {
+                Transform3D::rotation(0.0, 0.0, 1.0, angle)
+            }
+            TransformOp::RotateX(angle) => Transform3D::rotation(1.0, 0.0, 0.0, angle),
+            TransformOp::RotateY(angle) => Transform3D::rotation(0.0, 1.0, 0.0, angle),
+            TransformOp::Rotate3d(x, y, z, angle) => Transform3D::rotation(x, y, z, angle),
+            TransformOp::Skew(ax, ay) => Transform3D::skew(ax, ay),
+            TransformOp::SkewX(ax) => Transform3D::skew(ax, Angle::zero()),
+            TransformOp::SkewY(ay) => Transform3D::skew(Angle::zero(), ay),
+            TransformOp::Perspective(distance) => {
+                // CSS treats distances below 1px as 1px
+                let mut perspective = Transform3D::identity();
+                perspective.m34 = -1.0 / distance.max(1.0);
+                perspective
+            }
+            TransformOp::Matrix([a, b, c, d, e, f]) => Transform3D::new_2d(a, b, c, d, e, f),
+            TransformOp::Matrix3d(m) => Transform3D::from_array(m),
+        }
+    }
+}
+
+impl TransformOp {
+    /// Returns the identity function of the same kind, e.g. `translate(0px, 0px)` for a
+    /// translation. CSS pads the shorter list with these when interpolating.
+    fn identity(&self) -> TransformOp {
+        match *self {
+            TransformOp::Translate(..) => TransformOp::Translate(0.0, 0.0),
+            TransformOp::TranslateX(_) => TransformOp::TranslateX(0.0),
+            TransformOp::TranslateY(_) => TransformOp::TranslateY(0.0),
+            TransformOp::TranslateZ(_) => TransformOp::TranslateZ(0.0),
+            TransformOp::Translate3d(..) => TransformOp::Translate3d(0.0, 0.0, 0.0),
+            TransformOp::Scale(..) => TransformOp::Scale(1.0, 1.0),
+            TransformOp::ScaleX(_) => TransformOp::ScaleX(1.0),
+            TransformOp::ScaleY(_) => TransformOp::ScaleY(1.0),
+            TransformOp::ScaleZ(_) => TransformOp::ScaleZ(1.0),
+            TransformOp::Scale3d(..) => TransformOp::Scale3d(1.0, 1.0, 1.0),
+            TransformOp::Rotate(_) => TransformOp::Rotate(Angle::zero()),
+            TransformOp::RotateX(_) => TransformOp::RotateX(Angle::zero()),
+            TransformOp::RotateY(_) => TransformOp::RotateY(Angle::zero()),
+            TransformOp::RotateZ(_) => TransformOp::RotateZ(Angle::zero()),
+            TransformOp::Rotate3d(x, y, z, _) => TransformOp::Rotate3d(x, y, z, Angle::zero()),
+            TransformOp::Skew(..) => TransformOp::Skew(Angle::zero(), Angle::zero()),
+            TransformOp::SkewX(_) => TransformOp::SkewX(Angle::zero()),
+            TransformOp::SkewY(_) => TransformOp::SkewY(Angle::zero()),
+            TransformOp::Perspective(_) => TransformOp::Perspective(f32::INFINITY),
+            TransformOp::Matrix(_) => TransformOp::Matrix([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]),
+            TransformOp::Matrix3d(_) => TransformOp::Matrix3d(
+                Transform3D::<f32, UnknownUnit, UnknownUnit>::identity().to_array(),
+            ),
+        }
+    }
+
+    /// Interpolates the arguments of two functions of the same kind. Returns None when the
+    /// functions can't be interpolated argument by argument: different kinds, `rotate3d` around
+    /// different axes, or matrices, which CSS interpolates by decomposition instead.
+    fn interpolate(&self, to: &TransformOp, t: f32) -> Option<TransformOp> {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        let lerp_angle = |a: Angle<f32>, b: Angle<f32>| Angle::radians(lerp(a.radians, b.radians));
+
+        let op = match (*self, *to) {
+            (TransformOp::Translate(x0, y0), TransformOp::Translate(x1, y1)) => {
+                TransformOp::Translate(lerp(x0, x1), lerp(y0, y1))
+            }
+            (TransformOp::TranslateX(x0), TransformOp::TranslateX(x1)) => {
+                TransformOp::TranslateX(lerp(x0, x1))
+            }
+            (TransformOp::TranslateY(y0), TransformOp::TranslateY(y1)) => {
+                TransformOp::TranslateY(lerp(y0, y1))
+            }
+            (TransformOp::TranslateZ(z0), TransformOp::TranslateZ(z1)) => {
+                TransformOp::TranslateZ(lerp(z0, z1))
+            }
+            (TransformOp::Translate3d(x0, y0, z0), TransformOp::Translate3d(x1, y1, z1)) => {
+                TransformOp::Translate3d(lerp(x0, x1), lerp(y0, y1), lerp(z0, z1))
+            }
+            (TransformOp::Scale(x0, y0), TransformOp::Scale(x1, y1)) => {
+                TransformOp::Scale(lerp(x0, x1), lerp(y0, y1))
+            }
+            (TransformOp::ScaleX(x0), TransformOp::ScaleX(x1)) => TransformOp::ScaleX(lerp(x0, x1)),
+            (TransformOp::ScaleY(y0), TransformOp::ScaleY(y1)) => TransformOp::ScaleY(lerp(y0, y1)),
+            (TransformOp::ScaleZ(z0), TransformOp::ScaleZ(z1)) => TransformOp::ScaleZ(lerp(z0, z1)),
+            (TransformOp::Scale3d(x0, y0, z0), TransformOp::Scale3d(x1, y1, z1)) => {
+                TransformOp::Scale3d(lerp(x0, x1), lerp(y0, y1), lerp(z0, z1))
+            }
+            (TransformOp::Rotate(a0), TransformOp::Rotate(a1)) => {
+                TransformOp::Rotate(lerp_angle(a0, a1))
+            }
+            (TransformOp::RotateX(a0), TransformOp::RotateX(a1)) => {
+                TransformOp::RotateX(lerp_angle(a0, a1))
+            }
+            (TransformOp::RotateY(a0), TransformOp::RotateY(a1)) => {
+                TransformOp::RotateY(lerp_angle(a0, a1))
+            }
+            (TransformOp::RotateZ(a0), TransformOp::RotateZ(a1)) => {
+                TransformOp::RotateZ(lerp_angle(a0, a1))
+            }
+            (TransformOp::Rotate3d(x0, y0, z0, a0), TransformOp::Rotate3d(x1, y1, z1, a1)) => {
+                let axis0: euclid::Vector3D<f32, UnknownUnit> = euclid::Vector3D::new(x0, y0, z0);
+                let axis1 = euclid::Vector3D::new(x1, y1, z1);
+                if (axis0.normalize() - axis1.normalize()).length() > 1e-6 {
+                    return None;
+                }
+                TransformOp::Rotate3d(x0, y0, z0, lerp_angle(a0, a1))
+            }
+            (TransformOp::Skew(x0, y0), TransformOp::Skew(x1, y1)) => {
+                TransformOp::Skew(lerp_angle(x0, x1), lerp_angle(y0, y1))
+            }
+            (TransformOp::SkewX(a0), TransformOp::SkewX(a1)) => {
+                TransformOp::SkewX(lerp_angle(a0, a1))
+            }
+            (TransformOp::SkewY(a0), TransformOp::SkewY(a1)) => {
+                TransformOp::SkewY(lerp_angle(a0, a1))
+            }
+            (TransformOp::Perspective(d0), TransformOp::Perspective(d1)) => {
+                // Browsers interpolate the perspective divide, not the distance, so that
+                // interpolating from `perspective(none)` (an infinite distance) works. Distances
+                // below 1px are clamped first, the same as in `to_matrix`
+                TransformOp::Perspective(1.0 / lerp(1.0 / d0.max(1.0), 1.0 / d1.max(1.0)))
+            }
+            _ => return None,
+        };
+        Some(op)
+    }
+}
+
+impl fmt::Display for TransformOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            TransformOp::Translate(x, y) => write!(f, "translate({}px, {}px)", x, y),
+            TransformOp::TranslateX(x) => write!(f, "translateX({}px)", x),
+            TransformOp::TranslateY(y) => write!(f, "translateY({}px)", y),
+            TransformOp::TranslateZ(z) => write!(f, "translateZ({}px)", z),
+            TransformOp::Translate3d(x, y, z) => {
+                write!(f, "translate3d({}px, {}px, {}px)", x, y, z)
+            }
+            TransformOp::Scale(x, y) => write!(f, "scale({}, {})", x, y),
+            TransformOp::ScaleX(x) => write!(f, "scaleX({})", x),
+            TransformOp::ScaleY(y) => write!(f, "scaleY({})", y),
+            TransformOp::ScaleZ(z) => write!(f, "scaleZ({})", z),
+            TransformOp::Scale3d(x, y, z) => write!(f, "scale3d({}, {}, {})", x, y, z),
+            TransformOp::Rotate(angle) => write!(f, "rotate({}deg)", angle.to_degrees()),
+            TransformOp::RotateX(angle) => write!(f, "rotateX({}deg)", angle.to_degrees()),
+            TransformOp::RotateY(angle) => write!(f, "rotateY({}deg)", angle.to_degrees()),
+            TransformOp::RotateZ(angle) => write!(f, "rotateZ({}deg)", angle.to_degrees()),
+            TransformOp::Rotate3d(x, y, z, angle) => {
+                write!(
+                    f,
+                    "rotate3d({}, {}, {}, {}deg)",
+                    x,
+                    y,
+                    z,
+                    angle.to_degrees()
+                )
+            }
+            TransformOp::Skew(ax, ay) => {
+                write!(f, "skew({}deg, {}deg)", ax.to_degrees(), ay.to_degrees())
+            }
+            TransformOp::SkewX(ax) => write!(f, "skewX({}deg)", ax.to_degrees()),
+            TransformOp::SkewY(ay) => write!(f, "skewY({}deg)", ay.to_degrees()),
+            TransformOp::Perspective(distance) => write!(f, "perspective({}px)", distance),
+            TransformOp::Matrix(m) => write!(f, "matrix({})", join(&m)),
+            TransformOp::Matrix3d(m) => write!(f, "matrix3d({})", join(&m)),
+        }
+    }
+}
+
+fn join(values: &[f32]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Error returned when a CSS transform list can't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input ended in the middle of a function
+    UnexpectedEnd,
+    /// A character that can't start or continue a function
+    UnexpectedCharacter(char),
+    /// A function name this crate doesn't know
+    UnknownFunction(String),
+    /// A function got the wrong number of arguments
+    ArgumentCount(String),
+    /// An argument is not a valid number, length or angle
+    InvalidValue(String),
+    /// The input is valid, but not the single function that was asked for
+    ExpectedFunction(&'static str),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnexpectedEnd => write!(f, "unexpected end of transform list"),
+            ParseError::UnexpectedCharacter(c) => write!(f, "unexpected character '{}'", c),
+            ParseError::UnknownFunction(name) => write!(f, "unknown transform function '{}'", name),
+            ParseError::ArgumentCount(name) => {
+                write!(f, "wrong number of arguments for '{}'", name)
+            }
+            ParseError::InvalidValue(value) => write!(f, "invalid value '{}'", value),
+            ParseError::ExpectedFunction(name) => {
+                write!(f, "expected a single '{}' function", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a CSS transform list such as `"translate(10px, 20px) rotate(45deg)"`.
+/// `"none"` and the empty string parse to an empty list.
+pub fn parse_transform_list(s: &str) -> Result<Vec<TransformOp>, ParseError> {
+    let mut ops = Vec::new();
+    let mut rest = s.trim();
+    if rest == "none" {
+        return Ok(ops);
+    }
+
+    while !rest.is_empty() {
+        let open = rest.find('(').ok_or(ParseError::UnexpectedEnd)?;
+        let name = rest[..open].trim();
+        if let Some(c) = name.chars().find(|c| !c.is_ascii_alphanumeric()) {
+            return Err(ParseError::UnexpectedCharacter(c));
+        }
+        let close = rest.find(')').ok_or(ParseError::UnexpectedEnd)?;
+        if close < open {
+            return Err(ParseError::UnexpectedCharacter(')'));
+        }
+        let args: Vec<&str> = rest[open + 1..close].split(',').map(str::trim).collect();
+        ops.push(parse_function(name, &args)?);
+        rest = rest[close + 1..].trim_start();
+    }
+
+    Ok(ops)
+}
+
+fn parse_function(name: &str, args: &[&str]) -> Result<TransformOp, ParseError> {
+    let count_error = || ParseError::ArgumentCount(name.to_string());
+    let lengths = |min: usize, max: usize| -> Result<Vec<f32>, ParseError> {
+        if args.len() < min || args.len() > max {
+            return Err(count_error());
+        }
+        args.iter().map(|arg| parse_length(arg)).collect()
+    };
+    let numbers = |min: usize, max: usize| -> Result<Vec<f32>, ParseError> {
+        if args.len() < min || args.len() > max {
+            return Err(count_error());
+        }
+        args.iter().map(|arg| parse_number(arg)).collect()
+    };
+    let angles = |min: usize, max: usize| -> Result<Vec<Angle<f32>>, ParseError> {
+        if args.len() < min || args.len() > max {
+            return Err(count_error());
+        }
+        args.iter().map(|arg| parse_angle(arg)).collect()
+    };
+
+    let op = match name.to_ascii_lowercase().as_str() {
+        "translate" => {
+            let v = lengths(1, 2)?;
+            TransformOp::Translate(v[0], v.get(1).copied().unwrap_or(0.0))
+        }
+        "translatex" => TransformOp::TranslateX(lengths(1, 1)?[0]),
+        "translatey" => TransformOp::TranslateY(lengths(1, 1)?[0]),
+        "translatez" => TransformOp::TranslateZ(lengths(1, 1)?[0]),
+        "translate3d" => {
+            let v = lengths(3, 3)?;
+            TransformOp::Translate3d(v[0], v[1], v[2])
+        }
+        "scale" => {
+            let v = numbers(1, 2)?;
+            TransformOp::Scale(v[0], v.get(1).copied().unwrap_or(v[0]))
+        }
+        "scalex" => TransformOp::ScaleX(numbers(1, 1)?[0]),
+        "scaley" => TransformOp::ScaleY(numbers(1, 1)?[0]),
+        "scalez" => TransformOp::ScaleZ(numbers(1, 1)?[0]),
+        "scale3d" => {
+            let v = numbers(3, 3)?;
+            TransformOp::Scale3d(v[0], v[1], v[2])
+        }
+        "rotate" => TransformOp::Rotate(angles(1, 1)?[0]),
+        "rotatex" => TransformOp::RotateX(angles(1, 1)?[0]),
+        "rotatey" => TransformOp::RotateY(angles(1, 1)?[0]),
+        "rotatez" => TransformOp::RotateZ(angles(1, 1)?[0]),
+        "rotate3d" => {
+            if args.len() != 4 {
+                return Err(count_error());
+            }
+            TransformOp::Rotate3d(
+                parse_number(args[0])?,
+                parse_number(args[1])?,
+                parse_number(args[2])?,
+                parse_angle(args[3])?,
+            )
+        }
+        "skew" => {
+            let v = angles(1, 2)?;
+            TransformOp::Skew(v[0], v.get(1).copied().unwrap_or(Angle::zero()))
+        }
+        "skewx" => TransformOp::SkewX(angles(1, 1)?[0]),
+        "skewy" => TransformOp::SkewY(angles(1, 1)?[0]),
+        "perspective" => {
+            let distance = lengths(1, 1)?[0];
+            if distance < 0.0 {
+                return Err(ParseError::InvalidValue(args[0].to_string()));
+            }
+            TransformOp::Perspective(distance)
+        }
+        "matrix" => {
+            let v = numbers(6, 6)?;
+            TransformOp::Matrix([v[0], v[1], v[2], v[3], v[4], v[5]])
+        }
+        "matrix3d" => {
+            let v = numbers(16, 16)?;
+            let mut m = [0.0; 16];
+            m.copy_from_slice(&v);
+            TransformOp::Matrix3d(m)
+        }
+        _ => return Err(ParseError::UnknownFunction(name.to_string())),
+    };
+
+    Ok(op)
+}
+
+fn parse_number(s: &str) -> Result<f32, ParseError> {
+    s.parse::<f32>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .ok_or_else(|| ParseError::InvalidValue(s.to_string()))
+}
+
+/// Parses a length in pixels. Unitless values are accepted for compatibility with `matrix`-style
+/// inputs and SVG.
+fn parse_length(s: &str) -> Result<f32, ParseError> {
+    parse_number(s.strip_suffix("px").unwrap_or(s))
+}
+
+fn parse_angle(s: &str) -> Result<Angle<f32>, ParseError> {
+    let invalid = || ParseError::InvalidValue(s.to_string());
+    if let Some(v) = s.strip_suffix("deg") {
+        Ok(Angle::degrees(parse_number(v).map_err(|_| invalid())?))
+    } else if let Some(v) = s.strip_suffix("grad") {
+        Ok(Angle::degrees(
+            parse_number(v).map_err(|_| invalid())? * 0.9,
+        ))
+    } else if let Some(v) = s.strip_suffix("rad") {
+        Ok(Angle::radians(parse_number(v).map_err(|_| invalid())?))
+    } else if let Some(v) = s.strip_suffix("turn") {
+        Ok(Angle::degrees(
+            parse_number(v).map_err(|_| invalid())? * 360.0,
+        ))
+    } else if s == "0" {
+        Ok(Angle::zero())
+    } else {
+        Err(invalid())
+    }
+}
+
+impl Transform {
+    /// Creates a transform whose local transform is the given CSS transform list.
+    pub fn from_css_transform_list(s: &str) -> Result<Transform, ParseError> {
+        Ok(Transform::new().then_css_ops(&parse_transform_list(s)?))
+    }
+
+    /// Creates a transform from a CSS 2D `matrix(a, b, c, d, e, f)` function, which maps
+    /// (x, y) to (a * x + c * y + e, b * x + d * y + f).
+    pub fn from_css_matrix2d(s: &str) -> Result<Transform, ParseError> {
+        match parse_transform_list(s)?.as_slice() {
+            [op @ TransformOp::Matrix(_)] => Ok(Transform::new().then_css_ops(&[*op])),
+            _ => Err(ParseError::ExpectedFunction("matrix")),
+        }
+    }
+
+    /// Creates a transform from a CSS `matrix3d(...)` function.
+    pub fn from_css_matrix3d(s: &str) -> Result<Transform, ParseError> {
+        match parse_transform_list(s)?.as_slice() {
+            [op @ TransformOp::Matrix3d(_)] => Ok(Transform::new().then_css_ops(&[*op])),
+            _ => Err(ParseError::ExpectedFunction("matrix3d")),
+        }
+    }
+
+    /// Appends CSS transform functions to the local transform. As in CSS, the last function in
+    /// the list is applied to the element first.
+    pub fn then_css_ops(mut self, ops: &[TransformOp]) -> Self {
+        for op in ops.iter().rev() {
+            self.local_transform = self.local_transform.then(&op.to_matrix());
+        }
+        self
+    }
+
+    /// Returns a copy of this transform whose local transform is `t` of the way between two CSS
+    /// transform lists, following the CSS rules for animating the `transform` property.
+    ///
+    /// When both lists use the same functions in the same order (the shorter list is padded with
+    /// identity functions, so e.g. `none` works too), each function's arguments are
+    /// interpolated on their own, e.g. `rotate(0deg)` to `rotate(720deg)` spins twice. Otherwise
+    /// both lists are turned into matrices, which are decomposed into translation, rotation and
+    /// scale, interpolated, and composed again. Matrices with perspective, which can't be
+    /// decomposed, are interpolated entry by entry as a last resort.
+    pub fn interpolate_css(
+        &self,
+        from_list: &[TransformOp],
+        to_list: &[TransformOp],
+        t: f32,
+    ) -> Transform {
+        let len = from_list.len().max(to_list.len());
+        let function_wise: Option<Vec<TransformOp>> = (0..len)
+            .map(|i| {
+                let (from, to) = match (from_list.get(i), to_list.get(i)) {
+                    (Some(from), Some(to)) => (*from, *to),
+                    (Some(from), None) => (*from, from.identity()),
+                    (None, Some(to)) => (to.identity(), *to),
+                    (None, None) => unreachable!(),
+                };
+                from.interpolate(&to, t)
+            })
+            .collect();
+
+        let mut result = self.clone();
+        result.local_transform = match function_wise {
+            Some(ops) => Transform::new().then_css_ops(&ops).local_transform,
+            None => {
+                let from = Transform::new().then_css_ops(from_list);
+                let to = Transform::new().then_css_ops(to_list);
+                crate::interpolate_decomposed(&from, &to, t).unwrap_or_else(|| {
+                    crate::lerp_matrix(&from.local_transform, &to.local_transform, t)
+                })
+            }
+        };
+        result
+    }
+
+    /// Returns the local transform as a CSS transform list. The local transform is decomposed
+    /// into readable `translate`, `rotate` and `scale` functions when that reproduces it exactly,
+    /// otherwise it is written out as `matrix3d(...)`. An identity local transform gives `"none"`.
+    pub fn to_css_transform_list(&self) -> String {
+        if self.local_transform == Transform3D::identity() {
+            return "none".to_string();
+        }
+
+        let ops = self
+            .decompose()
+            .map(decomposition_to_ops)
+            .filter(|ops| {
+                let recomposed = Transform::new().then_css_ops(ops).local_transform;
+                recomposed
+                    .to_array()
+                    .iter()
+                    .zip(self.local_transform.to_array().iter())
+                    .all(|(a, b)| (a - b).abs() <= 1e-4 * b.abs().max(1.0))
+            })
+            .unwrap_or_else(|| vec![TransformOp::Matrix3d(self.local_transform.to_array())]);
+
+        ops.iter()
+            .map(|op| op.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn decomposition_to_ops(parts: crate::Decomposition) -> Vec<TransformOp> {
+    let mut ops = Vec::new();
+
+    let (tx, ty, tz) = parts.translation;
+    if tz != 0.0 {
+        ops.push(TransformOp::Translate3d(tx, ty, tz));
+    } else if tx != 0.0 || ty != 0.0 {
+        ops.push(TransformOp::Translate(tx, ty));
+    }
+
+    let [x, y, z, w] = parts.rotation;
+    let half_angle = w.clamp(-1.0, 1.0).acos();
+    let sin = half_angle.sin();
+    if sin.abs() > 1e-6 {
+        let angle = Angle::radians(half_angle * 2.0);
+        let (ax, ay, az) = (x / sin, y / sin, z / sin);
+        let op = if ay.abs() < 1e-6 && az.abs() < 1e-6 {
+            TransformOp::RotateX(angle * ax.signum())
+        } else if ax.abs() < 1e-6 && az.abs() < 1e-6 {
+            TransformOp::RotateY(angle * ay.signum())
+        } else if ax.abs() < 1e-6 && ay.abs() < 1e-6 {
+            TransformOp::Rotate(angle * az.signum())
+        } else {
+            TransformOp::Rotate3d(ax, ay, az, angle)
+        };
+        ops.push(op);
+    }
+
+    let (sx, sy, sz) = parts.scale;
+    if sz != 1.0 {
+        ops.push(TransformOp::Scale3d(sx, sy, sz));
+    } else if sx != 1.0 || sy != 1.0 {
+        ops.push(TransformOp::Scale(sx, sy));
+    }
+
+    ops
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::{parse_transform_list, ParseError, TransformOp};
+    use crate::Transform;
+    use euclid::Angle;
+
+    #[test]
+    pub fn test_parse_transform_list() {
+        let ops = parse_transform_list("translate(10px, 20px) rotateX(45deg) scale(2)").unwrap();
+        assert_eq!(
+            ops,
+            vec![
+                TransformOp::Translate(10.0, 20.0),
+                TransformOp::RotateX(Angle::degrees(45.0)),
+                TransformOp::Scale(2.0, 2.0),
+            ]
+        );
+
+        assert_eq!(parse_transform_list("none"), Ok(vec![]));
+        assert_eq!(
+            parse_transform_list("wobble(1px)"),
+            Err(ParseError::UnknownFunction("wobble".to_string()))
+        );
+        assert_eq!(
+            parse_transform_list("translate(10px"),
+            Err(ParseError::UnexpectedEnd)
+        );
+    }
+
+    #[test]
+    pub fn test_css_list_matches_builder_order() {
+        let from_css = Transform::from_css_transform_list("translate(10px, 0px) rotateX(45deg)")
+            .unwrap()
+            .local_transform;
+        let from_builder = Transform::new()
+            .then_rotate_x_deg(45.0)
+            .then_translate(10.0, 0.0)
+            .local_transform;
+        assert_eq!(from_css, from_builder);
+    }
+
+    #[test]
+    pub fn test_to_css_transform_list_roundtrip() {
+        let transform = Transform::new()
+            .then_scale(2.0, 3.0)
+            .then_rotate_x_deg(45.0)
+            .then_translate(10.0, 20.0);
+        let css = transform.to_css_transform_list();
+        assert!(css.starts_with("translate(10px, 20px) rotateX("), "{}", css);
+
+        let parsed = Transform::from_css_transform_list(&css).unwrap();
+        for (a, b) in parsed
+            .local_transform
+            .to_array()
+            .iter()
+            .zip(transform.local_transform.to_array().iter())
+        {
+            assert!((a - b).abs() < 1e-4, "Roundtrip deviated: {} vs {}", a, b);
+        }
+
+        assert_eq!(Transform::new().to_css_transform_list(), "none");
+        let skewed = Transform::from_css_transform_list("skewX(30deg)").unwrap();
+        assert!(skewed.to_css_transform_list().starts_with("matrix3d("));
+    }
+
+    #[test]
+    pub fn test_from_css_matrix2d() {
+        let transform = Transform::from_css_matrix2d("matrix(1, 0, 0, 1, 10, 20)").unwrap();
+        assert_eq!(
+            transform.local_transform,
+            Transform::new().then_translate(10.0, 20.0).local_transform
+        );
+
+        // matrix(a, b, c, d, e, f) maps (x, y) to (ax + cy + e, bx + dy + f)
+        let transform = Transform::from_css_matrix2d("matrix(1, 2, 3, 4, 5, 6)")
+            .unwrap()
+            .compose_2(&Transform::new());
+        assert_eq!(
+            transform.transform_local_point2d_to_world(1.0, 1.0),
+            (9.0, 12.0)
+        );
+
+        assert_eq!(
+            Transform::from_css_matrix2d("translate(10px, 20px)"),
+            Err(ParseError::ExpectedFunction("matrix"))
+        );
+    }
+
+    #[test]
+    pub fn test_interpolate_css() {
+        let root = Transform::new();
+        let base = Transform::new().with_position_relative_to_parent(100.0, 0.0);
+
+        // Matching lists interpolate the angle itself, so 720deg spins twice instead of not at all
+        let from = parse_transform_list("rotate(0deg)").unwrap();
+        let to = parse_transform_list("rotate(720deg)").unwrap();
+        let quarter = base.interpolate_css(&from, &to, 0.25).compose_2(&root);
+        let (x, y) = quarter.transform_local_point2d_to_world(10.0, 0.0);
+        assert!((x - 90.0).abs() < 1e-3 && y.abs() < 1e-3);
+
+        // `none` is padded with identity functions
+        let half = base
+            .interpolate_css(
+                &[],
+                &parse_transform_list("translate(20px, 40px)").unwrap(),
+                0.5,
+            )
+            .compose_2(&root);
+        assert_eq!(
+            half.transform_local_point2d_to_world(0.0, 0.0),
+            (110.0, 20.0)
+        );
+
+        // Different functions fall back to decomposition, which takes the shortest rotation
+        let from = parse_transform_list("rotate(0deg)").unwrap();
+        let to = parse_transform_list("scale(1) rotate(270deg)").unwrap();
+        let half = base.interpolate_css(&from, &to, 0.5).compose_2(&root);
+        let (x, y) = half.transform_local_point2d_to_world(10.0, 0.0);
+        let expected = Angle::degrees(-45.0f32);
+        assert!((x - (100.0 + 10.0 * expected.radians.cos())).abs() < 1e-3);
+        assert!((y - 10.0 * expected.radians.sin()).abs() < 1e-3);
+    }
+
+    #[test]
+    pub fn test_interpolate_perspective_from_zero() {
+        let from = parse_transform_list("perspective(0px)").unwrap();
+        let to = parse_transform_list("perspective(500px)").unwrap();
+        let m34 = |t: f32| {
+            Transform::new()
+                .interpolate_css(&from, &to, t)
+                .local_transform
+                .m34
+        };
+
+        assert_eq!(m34(0.0), -1.0);
+        assert!((m34(0.5) - (-1.0 - 1.0 / 500.0) / 2.0).abs() < 1e-6);
+        assert!((m34(1.0) - -1.0 / 500.0).abs() < 1e-7, "{}", m34(1.0));
+    }
+}
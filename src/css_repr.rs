@@ -0,0 +1,84 @@
+//! Serde adapter storing a [`Transform`] as a human-readable CSS transform list instead of raw
+//! matrices, for scene files that are meant to be diffed and edited by hand.
+//!
+//! Only the local transform is stored. The world transform, origin, position and perspective
+//! are not part of the CSS `transform` property and come back at their defaults, so the
+//! transform has to be composed again after deserializing.
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use transformator::Transform;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Card {
+//!     #[serde(with = "transformator::css_repr")]
+//!     transform: Transform,
+//! }
+//! ```
+
+use crate::Transform;
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+pub fn serialize<S>(transform: &Transform, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&transform.to_css_transform_list())
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Transform, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let css = String::deserialize(deserializer)?;
+    Transform::from_css_transform_list(&css).map_err(D::Error::custom)
+}
+
+#[cfg(all(test, feature = "serialization"))]
+pub mod tests {
+    use serde::de::value::{Error, StrDeserializer};
+    use serde::{Deserialize, Serialize};
+    use serde_test::{assert_ser_tokens, Token};
+
+    use crate::Transform;
+
+    #[derive(Serialize, Deserialize)]
+    struct Card {
+        #[serde(with = "crate::css_repr")]
+        transform: Transform,
+    }
+
+    #[test]
+    pub fn test_css_repr_roundtrip() {
+        let transform = Transform::new()
+            .then_scale(2.0, 3.0)
+            .then_translate(10.0, 20.0);
+        let css = "translate(10px, 20px) scale(2, 3)";
+        assert_ser_tokens(
+            &Card {
+                transform: transform.clone(),
+            },
+            &[
+                Token::Struct {
+                    name: "Card",
+                    len: 1,
+                },
+                Token::Str("transform"),
+                Token::Str(css),
+                Token::StructEnd,
+            ],
+        );
+
+        let deserialized = super::deserialize(StrDeserializer::<Error>::new(css)).unwrap();
+        for (a, e) in deserialized
+            .local_transform
+            .to_array()
+            .iter()
+            .zip(transform.local_transform.to_array())
+        {
+            assert!((a - e).abs() < 1e-5, "{} != {}", a, e);
+        }
+
+        assert!(super::deserialize(StrDeserializer::<Error>::new("rotate(45")).is_err());
+    }
+}
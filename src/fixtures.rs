@@ -0,0 +1,110 @@
+//! Reference values for the tests, measured in Chrome for the equivalent CSS-transformed
+//! elements.
+//!
+//! The scenes share one layout: a 100x100 parent centered in an 800x600 viewport, in a
+//! perspective container with a 500px distance and the vanishing point at the viewport center,
+//! rotated around its center. It contains two 35x80 children with 10px padding:
+//! padding(10) + rect(35) + gap(10) + rect(35) + padding(10) = 100 total width, and
+//! padding(10) + height(80) + padding(10) = 100 total height.
+//!
+//! The coordinates are VERY rough (+- 5 pixels) estimations measured by hovering the mouse, so
+//! compare against them with [`CHROME_TOLERANCE`]. Corners are clockwise, starting at the top
+//! left.
+
+use crate::Transform;
+
+pub(crate) type Corners = [(f32, f32); 4];
+
+pub(crate) const VIEWPORT_CENTER: (f32, f32) = (400.0, 300.0);
+pub(crate) const PERSPECTIVE_DISTANCE: f32 = 500.0;
+pub(crate) const RECT_SIZE: (f32, f32) = (100.0, 100.0);
+pub(crate) const INNER_RECT_SIZE: (f32, f32) = (35.0, 80.0);
+pub(crate) const CHILD1_POSITION: (f32, f32) = (10.0, 10.0);
+pub(crate) const CHILD2_POSITION: (f32, f32) = (55.0, 10.0);
+
+/// Maximum deviation from the values measured in Chrome, in pixels.
+pub(crate) const CHROME_TOLERANCE: f32 = 5.0;
+
+/// Parent rotated 45° around x.
+pub(crate) const ROTATE_X_PARENT: Corners = [
+    (346.0, 264.0),
+    (455.0, 264.0),
+    (465.0, 348.0),
+    (336.0, 348.0),
+];
+pub(crate) const ROTATE_X_CHILD1: Corners = [
+    (355.0, 270.0),
+    (395.0, 270.0),
+    (394.0, 338.0),
+    (348.0, 338.0),
+];
+pub(crate) const ROTATE_X_CHILD2: Corners = [
+    (406.0, 270.0),
+    (446.0, 270.0),
+    (453.0, 338.0),
+    (405.0, 338.0),
+];
+
+/// Parent rotated 30° around y, then 45° around x.
+pub(crate) const ROTATE_XY_PARENT: Corners = [
+    (352.0, 242.0),
+    (446.0, 285.0),
+    (455.0, 369.0),
+    (342.0, 327.0),
+];
+pub(crate) const ROTATE_XY_CHILD1: Corners = [
+    (360.0, 253.0),
+    (395.0, 268.0),
+    (395.0, 338.0),
+    (353.0, 321.0),
+];
+pub(crate) const ROTATE_XY_CHILD2: Corners = [
+    (405.0, 272.0),
+    (439.0, 287.0),
+    (446.0, 356.0),
+    (405.0, 341.0),
+];
+
+/// Children of the [`ROTATE_XY_PARENT`] parent, each rotated 20° around y around its center.
+pub(crate) const NESTED_ROTATE_Y_CHILD1: Corners = [
+    (364.0, 248.0),
+    (391.0, 272.0),
+    (390.0, 342.0),
+    (358.0, 317.0),
+];
+pub(crate) const NESTED_ROTATE_Y_CHILD2: Corners = [
+    (410.0, 269.0),
+    (436.0, 292.0),
+    (439.0, 360.0),
+    (410.0, 339.0),
+];
+
+/// Projects the corners of a `size` rect at the element's local origin, in [`Corners`] order.
+pub(crate) fn rect_corners(transform: &Transform, size: (f32, f32)) -> Corners {
+    [
+        transform.transform_local_point2d_to_world(0.0, 0.0),
+        transform.transform_local_point2d_to_world(size.0, 0.0),
+        transform.transform_local_point2d_to_world(size.0, size.1),
+        transform.transform_local_point2d_to_world(0.0, size.1),
+    ]
+}
+
+/// Asserts that every corner is within `tol` of the expected one on both axes.
+#[track_caller]
+pub(crate) fn assert_corners_within(actual: &[(f32, f32)], expected: &[(f32, f32)], tol: f32) {
+    assert_eq!(actual.len(), expected.len(), "corner count differs");
+    for (i, (actual_corner, expected_corner)) in actual.iter().zip(expected).enumerate() {
+        let dx = (actual_corner.0 - expected_corner.0).abs();
+        let dy = (actual_corner.1 - expected_corner.1).abs();
+        assert!(
+            dx < tol && dy < tol,
+            "Corner {} deviated: got {:?}, expected {:?}, delta=({},{}), all corners: {:?}",
+            i,
+            actual_corner,
+            expected_corner,
+            dx,
+            dy,
+            actual
+        );
+    }
+}
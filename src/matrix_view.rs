@@ -0,0 +1,42 @@
+use euclid::{Transform3D, UnknownUnit};
+
+/// Read-only view of a transform matrix with named entry accessors, e.g. `m34()` for the
+/// perspective term. Entries follow euclid's naming: `mRC` is row `R`, column `C`, 1-based, in
+/// the same row-major layout as [`crate::Transform::rows_world`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MatrixView<'a> {
+    matrix: &'a Transform3D<f32, UnknownUnit, UnknownUnit>,
+}
+
+macro_rules! entries {
+    ($($name:ident),*) => {
+        $(
+            pub fn $name(&self) -> f32 {
+                self.matrix.$name
+            }
+        )*
+    };
+}
+
+impl<'a> MatrixView<'a> {
+    pub fn new(matrix: &'a Transform3D<f32, UnknownUnit, UnknownUnit>) -> Self {
+        Self { matrix }
+    }
+
+    entries!(m11, m12, m13, m14, m21, m22, m23, m24, m31, m32, m33, m34, m41, m42, m43, m44);
+
+    /// Returns the row at the 0-based `index`. Panics if `index` is greater than 3.
+    pub fn row(&self, index: usize) -> [f32; 4] {
+        self.matrix.to_arrays()[index]
+    }
+
+    /// Returns the column at the 0-based `index`. Panics if `index` is greater than 3.
+    pub fn column(&self, index: usize) -> [f32; 4] {
+        self.matrix.to_arrays_transposed()[index]
+    }
+
+    /// Returns the underlying matrix.
+    pub fn matrix(&self) -> &'a Transform3D<f32, UnknownUnit, UnknownUnit> {
+        self.matrix
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{Decomposition, Transform};
+#[cfg(feature = "serialization")]
+use serde::{Deserialize, Serialize};
+
+/// Damped spring for animating a [`Transform`] towards a target over time.
+///
+/// Every component of the decomposed local transform (translation, rotation as a quaternion and
+/// scale) is driven by its own spring, and the velocities are kept between steps. Retargeting
+/// mid-animation therefore continues smoothly from the current velocity instead of restarting.
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spring {
+    pub stiffness: f32,
+    pub damping: f32,
+    pub translation_velocity: (f32, f32, f32),
+    /// Velocity of each quaternion component (x, y, z, w)
+    pub rotation_velocity: [f32; 4],
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub shear_velocity: (f32, f32, f32),
+    pub scale_velocity: (f32, f32, f32),
+}
+
+impl Spring {
+    pub fn new(stiffness: f32, damping: f32) -> Self {
+        Self {
+            stiffness,
+            damping,
+            translation_velocity: (0.0, 0.0, 0.0),
+            rotation_velocity: [0.0; 4],
+            shear_velocity: (0.0, 0.0, 0.0),
+            scale_velocity: (0.0, 0.0, 0.0),
+        }
+    }
+
+    /// Creates a spring that settles as fast as possible without overshooting.
+    pub fn critically_damped(stiffness: f32) -> Self {
+        Self::new(stiffness, 2.0 * stiffness.sqrt())
+    }
+
+    /// Returns whether all velocities are below `epsilon`.
+    pub fn is_at_rest(&self, epsilon: f32) -> bool {
+        let (tx, ty, tz) = self.translation_velocity;
+        let (hx, hy, hz) = self.shear_velocity;
+        let (sx, sy, sz) = self.scale_velocity;
+        [tx, ty, tz, hx, hy, hz, sx, sy, sz]
+            .iter()
+            .chain(self.rotation_velocity.iter())
+            .all(|v| v.abs() < epsilon)
+    }
+
+    /// Advances the spring by `dt` seconds and returns `current` moved towards `target`.
+    ///
+    /// Only the local transform is animated, the other fields are copied from `current`, and the
+    /// returned transform has to be composed again. If either local transform can't be
+    /// decomposed (e.g. it contains perspective), the target is returned and the velocities are
+    /// reset.
+    pub fn step(&mut self, current: &Transform, target: &Transform, dt: f32) -> Transform {
+        let (from, to) = match (current.decompose(), target.decompose()) {
+            (Some(from), Some(to)) => (from, to),
+            _ => {
+                *self = Spring::new(self.stiffness, self.damping);
+                let mut result = current.clone();
+                result.local_transform = target.local_transform;
+                return result;
+            }
+        };
+
+        let (stiffness, damping) = (self.stiffness, self.damping);
+        let advance = |value: f32, target: f32, velocity: &mut f32| {
+            // Semi-implicit Euler
+            let acceleration = -stiffness * (value - target) - damping * *velocity;
+            *velocity += acceleration * dt;
+            value + *velocity * dt
+        };
+
+        let v = &mut self.translation_velocity;
+        let translation = (
+            advance(from.translation.0, to.translation.0, &mut v.0),
+            advance(from.translation.1, to.translation.1, &mut v.1),
+            advance(from.translation.2, to.translation.2, &mut v.2),
+        );
+        let v = &mut self.shear_velocity;
+        let shear = (
+            advance(from.shear.0, to.shear.0, &mut v.0),
+            advance(from.shear.1, to.shear.1, &mut v.1),
+            advance(from.shear.2, to.shear.2, &mut v.2),
+        );
+        let v = &mut self.scale_velocity;
+        let scale = (
+            advance(from.scale.0, to.scale.0, &mut v.0),
+            advance(from.scale.1, to.scale.1, &mut v.1),
+            advance(from.scale.2, to.scale.2, &mut v.2),
+        );
+
+        // q and -q are the same rotation, spring towards the one on the shortest path
+        let mut to_rotation = to.rotation;
+        let dot: f32 = (0..4).map(|i| from.rotation[i] * to_rotation[i]).sum();
+        if dot < 0.0 {
+            to_rotation = to_rotation.map(|c| -c);
+        }
+        let mut rotation = [0.0; 4];
+        for i in 0..4 {
+            rotation[i] = advance(
+                from.rotation[i],
+                to_rotation[i],
+                &mut self.rotation_velocity[i],
+            );
+        }
+
+        let mut result = current.clone();
+        result.local_transform = Transform::from_trs_parts(Decomposition {
+            translation,
+            rotation,
+            shear,
+            scale,
+        })
+        .local_transform;
+        result
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::Spring;
+    use crate::Transform;
+
+    #[test]
+    pub fn test_spring_settles_on_target() {
+        let target = Transform::new()
+            .then_scale(2.0, 2.0)
+            .then_rotate_z_deg(90.0)
+            .then_translate(100.0, 50.0);
+        let mut current = Transform::new();
+        let mut spring = Spring::critically_damped(170.0);
+
+        for _ in 0..300 {
+            current = spring.step(&current, &target, 1.0 / 60.0);
+        }
+
+        assert!(spring.is_at_rest(1e-3));
+        for (a, b) in current
+            .local_transform
+            .to_array()
+            .iter()
+            .zip(target.local_transform.to_array().iter())
+        {
+            assert!((a - b).abs() < 1e-3, "Spring deviated: {} vs {}", a, b);
+        }
+    }
+}
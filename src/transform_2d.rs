@@ -0,0 +1,238 @@
+use crate::Transform;
+use euclid::{Angle, UnknownUnit};
+#[cfg(feature = "serialization")]
+use serde::{Deserialize, Serialize};
+
+/// A lightweight 2D-only counterpart of [`Transform`] for flat UIs.
+///
+/// It has the same composition rules (origin, position relative to parent, parent world
+/// transform), but is backed by 3x2 affine matrices, so composing is cheaper and mapping points
+/// doesn't need a homogeneous divide. There is no perspective and no rotation out of the screen
+/// plane; use [`Transform2D::upgrade`] if those are needed later.
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transform2D {
+    /// Local transform relative to parent
+    pub local_transform: euclid::Transform2D<f32, UnknownUnit, UnknownUnit>,
+    /// Fully composed world transform including all parent transforms
+    pub world_transform: euclid::Transform2D<f32, UnknownUnit, UnknownUnit>,
+    /// Origin relative to the shape (pivot)
+    pub origin: (f32, f32),
+    /// Layout position relative to the parent
+    pub position_relative_to_parent: (f32, f32),
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transform2D {
+    pub fn new() -> Self {
+        Self {
+            local_transform: euclid::Transform2D::identity(),
+            world_transform: euclid::Transform2D::identity(),
+            origin: (0.0, 0.0),
+            position_relative_to_parent: (0.0, 0.0),
+        }
+    }
+
+    /// Composes local transform with parent's world transform, and stores the result as this
+    /// transform's world transform. Parent should be composed before calling this method.
+    pub fn compose(&mut self, parent: &Transform2D) {
+        self.world_transform = euclid::Transform2D::translation(-self.origin.0, -self.origin.1)
+            .then(&self.local_transform)
+            .then(&euclid::Transform2D::translation(
+                self.origin.0 + self.position_relative_to_parent.0,
+                self.origin.1 + self.position_relative_to_parent.1,
+            ))
+            .then(&parent.world_transform);
+    }
+
+    pub fn compose_2(mut self, parent: &Transform2D) -> Self {
+        self.compose(parent);
+        self
+    }
+
+    pub fn set_origin(&mut self, ox: f32, oy: f32) {
+        self.origin = (ox, oy);
+    }
+
+    pub fn with_origin(mut self, ox: f32, oy: f32) -> Self {
+        self.set_origin(ox, oy);
+        self
+    }
+
+    pub fn set_position_relative_to_parent(&mut self, x: f32, y: f32) {
+        self.position_relative_to_parent = (x, y);
+    }
+
+    pub fn with_position_relative_to_parent(mut self, x: f32, y: f32) -> Self {
+        self.set_position_relative_to_parent(x, y);
+        self
+    }
+
+    pub fn translate(&mut self, tx: f32, ty: f32) {
+        self.local_transform = self
+            .local_transform
+            .then(&euclid::Transform2D::translation(tx, ty));
+    }
+
+    pub fn then_translate(mut self, tx: f32, ty: f32) -> Self {
+        self.translate(tx, ty);
+        self
+    }
+
+    pub fn rotate_z_deg(degrees: f32) -> Self {
+        Transform2D::new().then_rotate_z(Angle::degrees(degrees))
+    }
+
+    pub fn rotate_z_rad(radians: f32) -> Self {
+        Transform2D::new().then_rotate_z(Angle::radians(radians))
+    }
+
+    pub fn then_rotate_z_deg(self, degrees: f32) -> Self {
+        self.then_rotate_z(Angle::degrees(degrees))
+    }
+
+    pub fn then_rotate_z_rad(self, radians: f32) -> Self {
+        self.then_rotate_z(Angle::radians(radians))
+    }
+
+    fn then_rotate_z(mut self, angle: Angle<f32>) -> Self {
+        self.local_transform = self
+            .local_transform
+            .then(&euclid::Transform2D::rotation(angle));
+        self
+    }
+
+    pub fn scale(sx: f32, sy: f32) -> Self {
+        Transform2D::new().then_scale(sx, sy)
+    }
+
+    pub fn then_scale(mut self, sx: f32, sy: f32) -> Self {
+        self.local_transform = self.local_transform.then_scale(sx, sy);
+        self
+    }
+
+    /// Same as CSS `skew(ax, ay)`.
+    pub fn skew_deg(ax: f32, ay: f32) -> Self {
+        Transform2D::new().then_skew_deg(ax, ay)
+    }
+
+    pub fn skew_rad(ax: f32, ay: f32) -> Self {
+        Transform2D::new().then_skew_rad(ax, ay)
+    }
+
+    pub fn then_skew_deg(self, ax: f32, ay: f32) -> Self {
+        self.then_skew_rad(ax.to_radians(), ay.to_radians())
+    }
+
+    pub fn then_skew_rad(mut self, ax: f32, ay: f32) -> Self {
+        let (ax, ay) = (ax.tan(), ay.tan());
+        self.local_transform = self
+            .local_transform
+            .then(&euclid::Transform2D::new(1.0, ay, ax, 1.0, 0.0, 0.0));
+        self
+    }
+
+    /// Transforms a local point to world coordinates. This is a plain affine map, no divide.
+    pub fn transform_local_point2d_to_world(&self, x: f32, y: f32) -> (f32, f32) {
+        self.world_transform
+            .transform_point(euclid::Point2D::new(x, y))
+            .to_tuple()
+    }
+
+    /// Transforms a world point back to local coordinates.
+    /// Returns None if the transform is not invertible.
+    pub fn transform_world_point_to_local(&self, x: f32, y: f32) -> Option<(f32, f32)> {
+        Some(
+            self.world_transform
+                .inverse()?
+                .transform_point(euclid::Point2D::new(x, y))
+                .to_tuple(),
+        )
+    }
+
+    /// Converts into a full 3D [`Transform`] with the same local and world transforms, for when
+    /// perspective or 3D rotations are needed after all.
+    pub fn upgrade(&self) -> Transform {
+        let mut transform = Transform::new()
+            .with_origin(self.origin.0, self.origin.1)
+            .with_position_relative_to_parent(
+                self.position_relative_to_parent.0,
+                self.position_relative_to_parent.1,
+            );
+        transform.local_transform = self.local_transform.to_3d();
+        transform.world_transform = self.world_transform.to_3d();
+        // A 2D world transform never has perspective
+        transform.world_affine = transform.world_transform;
+        transform
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::Transform2D;
+    use crate::Transform;
+
+    #[test]
+    pub fn test_matches_3d_transform() {
+        let parent_2d = Transform2D::new()
+            .with_position_relative_to_parent(100.0, 50.0)
+            .with_origin(50.0, 50.0)
+            .then_rotate_z_deg(30.0)
+            .then_scale(2.0, 1.5)
+            .compose_2(&Transform2D::new());
+        let child_2d = Transform2D::new()
+            .with_position_relative_to_parent(10.0, 10.0)
+            .then_skew_deg(10.0, 0.0)
+            .compose_2(&parent_2d);
+
+        let parent_3d = Transform::new()
+            .with_position_relative_to_parent(100.0, 50.0)
+            .with_origin(50.0, 50.0)
+            .then_rotate_z_deg(30.0)
+            .then_scale(2.0, 1.5)
+            .compose_2(&Transform::new());
+        let child_3d = Transform::from_css_transform_list("skewX(10deg)")
+            .unwrap()
+            .with_position_relative_to_parent(10.0, 10.0)
+            .compose_2(&parent_3d);
+
+        for (x, y) in [(0.0, 0.0), (35.0, 0.0), (35.0, 80.0), (0.0, 80.0)] {
+            let a = child_2d.transform_local_point2d_to_world(x, y);
+            let b = child_3d.transform_local_point2d_to_world(x, y);
+            let c = child_2d.upgrade().transform_local_point2d_to_world(x, y);
+            assert!(
+                (a.0 - b.0).abs() < 1e-3 && (a.1 - b.1).abs() < 1e-3,
+                "2D point deviated: got {:?}, expected {:?}",
+                a,
+                b
+            );
+            assert!((a.0 - c.0).abs() < 1e-3 && (a.1 - c.1).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    pub fn test_upgrade_keeps_orientation() {
+        let upgraded = Transform2D::new()
+            .then_rotate_z_deg(90.0)
+            .compose_2(&Transform2D::new())
+            .upgrade();
+        let composed = Transform::new()
+            .then_rotate_z_deg(90.0)
+            .compose_2(&Transform::new());
+
+        let a = upgraded.world_orientation_quaternion();
+        let b = composed.world_orientation_quaternion();
+        assert!(
+            a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-5),
+            "{:?} vs {:?}",
+            a,
+            b
+        );
+        assert!((b[2] - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::Transform;
+use euclid::{Point2D, UnknownUnit};
+
+/// Types that can be mapped from an element's local space to world space by a [`Transform`].
+///
+/// Implement this for your own geometry (segments, curves, paths) to pass it through the same
+/// pipeline as the built-in point types.
+///
+/// ```rust
+/// use transformator::{Transform, Transformable};
+///
+/// struct Segment((f32, f32), (f32, f32));
+///
+/// impl Transformable for Segment {
+///     fn transform_by(&self, t: &Transform) -> Self {
+///         Segment(self.0.transform_by(t), self.1.transform_by(t))
+///     }
+/// }
+///
+/// let transform = Transform::new()
+///     .with_position_relative_to_parent(10.0, 20.0)
+///     .compose_2(&Transform::new());
+/// let segment = Segment((0.0, 0.0), (5.0, 0.0)).transform_by(&transform);
+/// assert_eq!(segment.1, (15.0, 20.0));
+/// ```
+pub trait Transformable {
+    /// Returns a copy mapped from local to world coordinates using `t`'s world transform.
+    fn transform_by(&self, t: &Transform) -> Self;
+}
+
+impl Transformable for (f32, f32) {
+    fn transform_by(&self, t: &Transform) -> Self {
+        t.transform_local_point2d_to_world(self.0, self.1)
+    }
+}
+
+impl Transformable for Point2D<f32, UnknownUnit> {
+    fn transform_by(&self, t: &Transform) -> Self {
+        t.transform_local_point2d_to_world(self.x, self.y).into()
+    }
+}
+
+impl<T: Transformable, const N: usize> Transformable for [T; N] {
+    fn transform_by(&self, t: &Transform) -> Self {
+        std::array::from_fn(|i| self[i].transform_by(t))
+    }
+}
+
+impl<T: Transformable> Transformable for Vec<T> {
+    fn transform_by(&self, t: &Transform) -> Self {
+        self.iter().map(|item| item.transform_by(t)).collect()
+    }
+}
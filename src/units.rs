@@ -0,0 +1,51 @@
+//! Typed-unit wrappers around the point transform methods, so local and screen coordinates can't
+//! be mixed up by accident.
+//!
+//! The rest of the crate works with plain `(f32, f32)` tuples and [`euclid::UnknownUnit`]
+//! matrices. The methods here take and return euclid points tagged with [`LocalSpace`] or
+//! [`ScreenSpace`], and passing a screen point where a local one is expected is a compile error.
+//! They are separate methods rather than different signatures for the existing ones, so crates
+//! that don't enable the feature are unaffected when another crate in the build enables it.
+//!
+//! ```rust
+//! use transformator::units::{LocalPoint, ScreenPoint};
+//! use transformator::Transform;
+//!
+//! let transform = Transform::new()
+//!     .with_position_relative_to_parent(100.0, 50.0)
+//!     .compose_2(&Transform::new());
+//!
+//! let screen: ScreenPoint = transform.transform_local_point_typed(LocalPoint::new(10.0, 10.0));
+//! assert_eq!(screen, ScreenPoint::new(110.0, 60.0));
+//!
+//! let local = transform.project_screen_point_to_local_typed(screen).unwrap();
+//! assert_eq!(local, LocalPoint::new(10.0, 10.0));
+//! ```
+
+use crate::Transform;
+use euclid::Point2D;
+
+/// Unit of the element's own coordinate space, before any transform is applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LocalSpace {}
+
+/// Unit of the screen (world) space, after the composed world transform is applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScreenSpace {}
+
+pub type LocalPoint = Point2D<f32, LocalSpace>;
+pub type ScreenPoint = Point2D<f32, ScreenSpace>;
+
+impl Transform {
+    /// Same as [`Transform::transform_local_point2d_to_world`], with typed points.
+    pub fn transform_local_point_typed(&self, point: LocalPoint) -> ScreenPoint {
+        let (x, y) = self.transform_local_point2d_to_world(point.x, point.y);
+        ScreenPoint::new(x, y)
+    }
+
+    /// Same as [`Transform::project_screen_point_to_local_2d`], with typed points.
+    pub fn project_screen_point_to_local_typed(&self, point: ScreenPoint) -> Option<LocalPoint> {
+        self.project_screen_point_to_local_2d((point.x, point.y))
+            .map(|(x, y)| LocalPoint::new(x, y))
+    }
+}